@@ -0,0 +1,365 @@
+// Full configuration-interaction post-HF module, following QCaml's CI module: the SCF MO
+// coefficients are used to transform the one- and two-electron integrals into the MO basis, a
+// determinant space is built, and the lowest eigenpair of the (never fully materialized) CI
+// Hamiltonian is found via the Davidson iterative method - only the diagonal and the
+// matrix-vector action of H are ever needed.
+
+use ndarray::{Array1, Array2, Array4};
+use ndarray_linalg::Eig;
+
+// One- and two-electron integrals in the molecular-orbital basis. The two-electron integrals use
+// chemist notation (pq|rs), same layout as the AO ERI tensor in `integrals`.
+pub struct MoIntegrals {
+    pub h_core: Array2<f64>,
+    pub eri: Array4<f64>,
+}
+
+pub fn ao_to_mo(h_core_ao: &Array2<f64>, eri_ao: &Array4<f64>, c: &Array2<f64>) -> MoIntegrals {
+    let h_core = c.t().dot(h_core_ao).dot(c);
+    let eri = ao_to_mo_eri(eri_ao, c);
+    MoIntegrals { h_core, eri }
+}
+
+// Four quarter transforms, O(n^5) each - fine for the small active spaces full CI is used on.
+fn ao_to_mo_eri(eri_ao: &Array4<f64>, c: &Array2<f64>) -> Array4<f64> {
+    let n = c.nrows();
+
+    let mut step1 = Array4::<f64>::zeros((n, n, n, n));
+    for p in 0..n {
+        for mu in 0..n {
+            let coeff = c[[mu, p]];
+            if coeff == 0.0 { continue; }
+            for nu in 0..n {
+                for lam in 0..n {
+                    for sig in 0..n {
+                        step1[[p, nu, lam, sig]] += coeff * eri_ao[[mu, nu, lam, sig]];
+                    }
+                }
+            }
+        }
+    }
+
+    let mut step2 = Array4::<f64>::zeros((n, n, n, n));
+    for p in 0..n {
+        for q in 0..n {
+            for nu in 0..n {
+                let coeff = c[[nu, q]];
+                if coeff == 0.0 { continue; }
+                for lam in 0..n {
+                    for sig in 0..n {
+                        step2[[p, q, lam, sig]] += coeff * step1[[p, nu, lam, sig]];
+                    }
+                }
+            }
+        }
+    }
+
+    let mut step3 = Array4::<f64>::zeros((n, n, n, n));
+    for p in 0..n {
+        for q in 0..n {
+            for r in 0..n {
+                for lam in 0..n {
+                    let coeff = c[[lam, r]];
+                    if coeff == 0.0 { continue; }
+                    for sig in 0..n {
+                        step3[[p, q, r, sig]] += coeff * step2[[p, q, lam, sig]];
+                    }
+                }
+            }
+        }
+    }
+
+    let mut mo = Array4::<f64>::zeros((n, n, n, n));
+    for p in 0..n {
+        for q in 0..n {
+            for r in 0..n {
+                for s in 0..n {
+                    for sig in 0..n {
+                        mo[[p, q, r, s]] += c[[sig, s]] * step3[[p, q, r, sig]];
+                    }
+                }
+            }
+        }
+    }
+
+    mo
+}
+
+// A determinant is a pair of occupation bitmasks over the spatial MO indices, one per spin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Determinant {
+    pub alpha: u64,
+    pub beta: u64,
+}
+
+fn occupied_orbitals(mask: u64) -> impl Iterator<Item = usize> {
+    (0..64).filter(move |&i| (mask >> i) & 1 == 1)
+}
+
+// All n_orbitals-choose-k occupation bitmasks.
+fn combinations(n_orbitals: usize, k: usize) -> Vec<u64> {
+    let mut result = Vec::new();
+    fn helper(n: usize, k: usize, start: usize, current: u64, result: &mut Vec<u64>) {
+        if k == 0 {
+            result.push(current);
+            return;
+        }
+        if n - start < k {
+            return;
+        }
+        for i in start..n {
+            helper(n, k - 1, i + 1, current | (1u64 << i), result);
+        }
+    }
+    helper(n_orbitals, k, 0, 0, &mut result);
+    result
+}
+
+// Every determinant formed by distributing n_alpha/n_beta electrons over n_orbitals spatial MOs.
+pub fn build_determinants(n_orbitals: usize, n_alpha: usize, n_beta: usize) -> Vec<Determinant> {
+    let alpha_strings = combinations(n_orbitals, n_alpha);
+    let beta_strings = combinations(n_orbitals, n_beta);
+
+    let mut dets = Vec::with_capacity(alpha_strings.len() * beta_strings.len());
+    for &alpha in &alpha_strings {
+        for &beta in &beta_strings {
+            dets.push(Determinant { alpha, beta });
+        }
+    }
+    dets
+}
+
+// Second-quantization sign picked up by applying (annihilate `from`, create `to`) excitations in
+// order against an occupation string, counting occupied orbitals crossed by each operator.
+fn excitation_sign(mut occ: u64, excitations: &[(usize, usize)]) -> f64 {
+    let mut sign = 1.0;
+    for &(from, to) in excitations {
+        let below_from = (occ & ((1u64 << from) - 1)).count_ones();
+        if below_from % 2 == 1 { sign = -sign; }
+        occ &= !(1u64 << from);
+
+        let below_to = (occ & ((1u64 << to) - 1)).count_ones();
+        if below_to % 2 == 1 { sign = -sign; }
+        occ |= 1u64 << to;
+    }
+    sign
+}
+
+// Slater-Condon matrix element <I|H|J> between two determinants over the MO integrals.
+fn slater_condon_element(det_i: &Determinant, det_j: &Determinant, mo: &MoIntegrals) -> f64 {
+    let alpha_diff_i = det_i.alpha & !det_j.alpha; // occupied in I, not in J
+    let alpha_diff_j = det_j.alpha & !det_i.alpha; // occupied in J, not in I
+    let beta_diff_i = det_i.beta & !det_j.beta;
+    let beta_diff_j = det_j.beta & !det_i.beta;
+
+    let n_diff_alpha = alpha_diff_i.count_ones();
+    let n_diff_beta = beta_diff_i.count_ones();
+
+    match (n_diff_alpha, n_diff_beta) {
+        (0, 0) => diagonal_element(det_i, mo),
+
+        (1, 0) => {
+            let i = alpha_diff_i.trailing_zeros() as usize;
+            let a = alpha_diff_j.trailing_zeros() as usize;
+            let sign = excitation_sign(det_i.alpha, &[(i, a)]);
+            sign * single_excitation_element(i, a, det_i.alpha & det_j.alpha, det_i.beta, mo)
+        }
+        (0, 1) => {
+            let i = beta_diff_i.trailing_zeros() as usize;
+            let a = beta_diff_j.trailing_zeros() as usize;
+            let sign = excitation_sign(det_i.beta, &[(i, a)]);
+            sign * single_excitation_element(i, a, det_i.beta & det_j.beta, det_i.alpha, mo)
+        }
+
+        (2, 0) => double_same_spin_element(alpha_diff_i, alpha_diff_j, det_i.alpha, mo),
+        (0, 2) => double_same_spin_element(beta_diff_i, beta_diff_j, det_i.beta, mo),
+
+        (1, 1) => {
+            let i = alpha_diff_i.trailing_zeros() as usize;
+            let a = alpha_diff_j.trailing_zeros() as usize;
+            let j = beta_diff_i.trailing_zeros() as usize;
+            let b = beta_diff_j.trailing_zeros() as usize;
+            let sign = excitation_sign(det_i.alpha, &[(i, a)]) * excitation_sign(det_i.beta, &[(j, b)]);
+            sign * mo.eri[[i, a, j, b]]
+        }
+
+        _ => 0.0, // more than a double excitation apart
+    }
+}
+
+fn diagonal_element(det: &Determinant, mo: &MoIntegrals) -> f64 {
+    let mut value = 0.0;
+
+    for p in occupied_orbitals(det.alpha) {
+        value += mo.h_core[[p, p]];
+    }
+    for p in occupied_orbitals(det.beta) {
+        value += mo.h_core[[p, p]];
+    }
+
+    let alpha_occ: Vec<usize> = occupied_orbitals(det.alpha).collect();
+    let beta_occ: Vec<usize> = occupied_orbitals(det.beta).collect();
+
+    for &p in &alpha_occ {
+        for &q in &alpha_occ {
+            value += 0.5 * (mo.eri[[p, p, q, q]] - mo.eri[[p, q, q, p]]);
+        }
+    }
+    for &p in &beta_occ {
+        for &q in &beta_occ {
+            value += 0.5 * (mo.eri[[p, p, q, q]] - mo.eri[[p, q, q, p]]);
+        }
+    }
+    for &p in &alpha_occ {
+        for &q in &beta_occ {
+            value += mo.eri[[p, p, q, q]];
+        }
+    }
+
+    value
+}
+
+// <I|H|J> for a single excitation i->a in one spin channel, with `common` the set of spin
+// orbitals of the *same* spin occupied in both determinants, and `other_spin_occ` the (unchanged)
+// occupation of the other spin channel.
+fn single_excitation_element(i: usize, a: usize, common: u64, other_spin_occ: u64, mo: &MoIntegrals) -> f64 {
+    let mut value = mo.h_core[[i, a]];
+
+    for m in occupied_orbitals(common) {
+        value += mo.eri[[i, a, m, m]] - mo.eri[[i, m, m, a]];
+    }
+    for m in occupied_orbitals(other_spin_occ) {
+        value += mo.eri[[i, a, m, m]];
+    }
+
+    value
+}
+
+// <I|H|J> for a same-spin double excitation, given the two occupied orbitals unique to I (sorted
+// ascending) and the two unique to J (sorted ascending, paired positionally with I's).
+fn double_same_spin_element(diff_i: u64, diff_j: u64, occ_i: u64, mo: &MoIntegrals) -> f64 {
+    let i: Vec<usize> = occupied_orbitals(diff_i).collect();
+    let a: Vec<usize> = occupied_orbitals(diff_j).collect();
+    let (i1, i2) = (i[0], i[1]);
+    let (a1, a2) = (a[0], a[1]);
+
+    let sign = excitation_sign(occ_i, &[(i1, a1), (i2, a2)]);
+    sign * (mo.eri[[i1, a1, i2, a2]] - mo.eri[[i1, a2, i2, a1]])
+}
+
+pub fn diagonal(dets: &[Determinant], mo: &MoIntegrals) -> Array1<f64> {
+    Array1::from_iter(dets.iter().map(|det| diagonal_element(det, mo)))
+}
+
+// sigma = H . b, computed without ever materializing the dense CI matrix.
+pub fn matrix_vector_product(dets: &[Determinant], mo: &MoIntegrals, b: &Array1<f64>) -> Array1<f64> {
+    let n = dets.len();
+    let mut sigma = Array1::<f64>::zeros(n);
+    for i in 0..n {
+        let mut val = 0.0;
+        for j in 0..n {
+            if b[j] == 0.0 {
+                continue;
+            }
+            val += slater_condon_element(&dets[i], &dets[j], mo) * b[j];
+        }
+        sigma[i] = val;
+    }
+    sigma
+}
+
+pub struct DavidsonResult {
+    pub eigenvalue: f64,
+    pub eigenvector: Array1<f64>,
+}
+
+// Davidson iterative eigensolver for the lowest root: maintain a small orthonormal trial
+// subspace {b}, form sigma = H.b, diagonalize the projected matrix B^T.sigma, take its lowest
+// Ritz pair, precondition the residual with the diagonal of H, Gram-Schmidt it against the
+// subspace, and append it - collapsing back to the current best vector once the subspace reaches
+// `max_subspace` vectors.
+pub fn davidson_lowest_eigenpair(
+    dets: &[Determinant],
+    mo: &MoIntegrals,
+    max_subspace: usize,
+    tol: f64,
+    max_iter: usize,
+) -> DavidsonResult {
+    let n = dets.len();
+    let diag = diagonal(dets, mo);
+
+    let mut guess_idx = 0;
+    for i in 1..n {
+        if diag[i] < diag[guess_idx] {
+            guess_idx = i;
+        }
+    }
+    let mut b0 = Array1::<f64>::zeros(n);
+    b0[guess_idx] = 1.0;
+
+    let mut basis = vec![b0.clone()];
+    let mut sigma = vec![matrix_vector_product(dets, mo, &b0)];
+
+    let mut theta = diag[guess_idx];
+    let mut ritz_vector = basis[0].clone();
+
+    for _ in 0..max_iter {
+        let m = basis.len();
+        let mut projected = Array2::<f64>::zeros((m, m));
+        for row in 0..m {
+            for col in 0..m {
+                projected[[row, col]] = basis[row].dot(&sigma[col]);
+            }
+        }
+
+        let (eigvals, eigvecs) = projected.eig().expect("Davidson subspace eigendecomposition failed");
+        let mut lowest = 0;
+        for i in 1..m {
+            if eigvals[i].re < eigvals[lowest].re {
+                lowest = i;
+            }
+        }
+        theta = eigvals[lowest].re;
+        let y = eigvecs.column(lowest).map(|v| v.re);
+
+        ritz_vector = Array1::<f64>::zeros(n);
+        let mut h_ritz = Array1::<f64>::zeros(n);
+        for k in 0..m {
+            ritz_vector = &ritz_vector + &(&basis[k] * y[k]);
+            h_ritz = &h_ritz + &(&sigma[k] * y[k]);
+        }
+
+        let residual = &h_ritz - &(&ritz_vector * theta);
+        let residual_norm = residual.dot(&residual).sqrt();
+        if residual_norm < tol {
+            break;
+        }
+
+        let mut delta = Array1::<f64>::zeros(n);
+        for i in 0..n {
+            let denom = theta - diag[i];
+            delta[i] = if denom.abs() > 1e-10 { residual[i] / denom } else { residual[i] };
+        }
+
+        // Gram-Schmidt orthogonalize against the current subspace
+        for b in &basis {
+            let overlap = delta.dot(b);
+            delta = &delta - &(b * overlap);
+        }
+        let norm = delta.dot(&delta).sqrt();
+        if norm < 1e-10 {
+            break; // subspace can't usefully be expanded further
+        }
+        delta /= norm;
+
+        basis.push(delta.clone());
+        sigma.push(matrix_vector_product(dets, mo, &delta));
+
+        if basis.len() >= max_subspace {
+            basis = vec![ritz_vector.clone()];
+            sigma = vec![matrix_vector_product(dets, mo, &ritz_vector)];
+        }
+    }
+
+    DavidsonResult { eigenvalue: theta, eigenvector: ritz_vector }
+}
@@ -2,112 +2,380 @@ use std::f64::consts::PI;
 use ndarray::Array2;
 use ndarray::Array4;
 
-use crate::BasisSetData;
+use crate::molecule::{CenteredBasisFunction, Molecule};
 
 #[inline]
 pub fn dist_sq(r1: &[f64; 3], r2: &[f64; 3]) -> f64 {
     (r1[0] - r2[0]).powi(2) + (r1[1] - r2[1]).powi(2) + (r1[2] - r2[2]).powi(2)
 }
 
-pub fn compute_s_primitive (alpha: f64, beta: f64, r_a: &[f64; 3], r_b: &[f64; 3]) -> f64 {
-    // Normalisation factor for primitive s-type Gaussian functions
-    let norm_factor = (2.0 * alpha / PI).powf(0.75) * (2.0 * beta / PI).powf(0.75);
+// Cartesian angular momentum (lx, ly, lz), e.g. (0,0,0) for s, (1,0,0) for px, (1,1,0) for dxy.
+pub type AngularMomentum = (i32, i32, i32);
 
-    let prefactor = (PI / (alpha + beta)).powf(1.5);
-    let exponent =  -(alpha * beta / (alpha + beta)) * dist_sq(r_a, r_b);
-
-    norm_factor * prefactor * exponent.exp()
+fn double_factorial(n: i32) -> f64 {
+    if n <= 0 {
+        return 1.0; // covers both 0!! = 1 and the (-1)!! = 1 convention used by cartesian_norm
+    }
+    let mut result = 1.0;
+    let mut k = n;
+    while k > 0 {
+        result *= k as f64;
+        k -= 2;
+    }
+    result
 }
 
-pub fn compute_t_primitive (alpha: f64,  beta: f64, r_a: &[f64; 3], r_b: &[f64; 3], s_prim: f64) -> f64 {
-    let reduced_exponent = (alpha * beta) / (alpha + beta);
+// Normalisation constant for a Cartesian Gaussian primitive x^l y^m z^n exp(-alpha*r^2). Reduces
+// to the plain s-norm (2a/pi)^0.75 when l=m=n=0.
+fn cartesian_norm(alpha: f64, (l, m, n): AngularMomentum) -> f64 {
+    let total_l = l + m + n;
+    let df = double_factorial(2 * l - 1) * double_factorial(2 * m - 1) * double_factorial(2 * n - 1);
+    (2.0 * alpha / PI).powf(0.75) * ((4.0 * alpha).powi(total_l) / df).sqrt()
+}
 
-    reduced_exponent * (3.0 - 2.0 * reduced_exponent * dist_sq(r_a, r_b)) * s_prim
+// McMurchie-Davidson Hermite expansion coefficient E_t^{ij} along one Cartesian axis, where qx is
+// the separation A-B along that axis. Base case E_0^{00} = exp(-mu*qx^2); higher (i,j,t) follow
+// the standard two-term recurrence, always decrementing whichever of i, j is still nonzero.
+fn hermite_e(i: i32, j: i32, t: i32, qx: f64, a: f64, b: f64) -> f64 {
+    if i < 0 || j < 0 || t < 0 || t > i + j {
+        return 0.0;
+    }
+    let p = a + b;
+    if i == 0 && j == 0 {
+        let mu = a * b / p;
+        return if t == 0 { (-mu * qx * qx).exp() } else { 0.0 };
+    }
+    if i > 0 {
+        let x_pa = -(b / p) * qx;
+        (1.0 / (2.0 * p)) * hermite_e(i - 1, j, t - 1, qx, a, b)
+            + x_pa * hermite_e(i - 1, j, t, qx, a, b)
+            + (t + 1) as f64 * hermite_e(i - 1, j, t + 1, qx, a, b)
+    } else {
+        let x_pb = (a / p) * qx;
+        (1.0 / (2.0 * p)) * hermite_e(i, j - 1, t - 1, qx, a, b)
+            + x_pb * hermite_e(i, j - 1, t, qx, a, b)
+            + (t + 1) as f64 * hermite_e(i, j - 1, t + 1, qx, a, b)
+    }
 }
 
-pub fn compute_v_nuc_primitive (alpha: f64, beta: f64, r_a: &[f64; 3], r_b: &[f64; 3], r_nuc: &[f64; 3], z_nuc: f64) -> f64 {
-    let norm_factor = (2.0 * alpha / PI).powf(0.75) * (2.0 * beta / PI).powf(0.75);
+// Boys function F_n(x) for n = 0..=n_max. For x > 35 the asymptotic F_0 plus stable upward
+// recursion is used; otherwise F_{n_max} is computed by series expansion and the rest come from
+// the (numerically stable) downward recursion.
+fn boys_function(n_max: usize, x: f64) -> Vec<f64> {
+    let mut f = vec![0.0; n_max + 1];
 
-    let zeta = alpha + beta;
-    // Compute the weighted product center of the two Gaussians
-    let r_p = [
-        (alpha * r_a[0] + beta * r_b[0]) / zeta,
-        (alpha * r_a[1] + beta * r_b[1]) / zeta,
-        (alpha * r_a[2] + beta * r_b[2]) / zeta,
-    ];
+    if x < 1e-12 {
+        for (n, f_n) in f.iter_mut().enumerate() {
+            *f_n = 1.0 / (2.0 * n as f64 + 1.0);
+        }
+        return f;
+    }
 
-   let ab_sq = (r_a[0] - r_b[0]).powi(2) + (r_a[1] - r_b[1]).powi(2) + (r_a[2] - r_b[2]).powi(2);
-   let k_ab = (-(alpha * beta / zeta * ab_sq)).exp();
+    if x > 35.0 {
+        f[0] = 0.5 * (PI / x).sqrt();
+        for n in 1..=n_max {
+            f[n] = ((2 * n - 1) as f64 * f[n - 1]) / (2.0 * x);
+        }
+        return f;
+    }
 
-   // Distance from the product center to the nucleus
-    let rp_nuc_sq = (r_p[0] - r_nuc[0]).powi(2) + (r_p[1] - r_nuc[1]).powi(2) + (r_p[2] - r_nuc[2]).powi(2);
+    let mut term = 1.0 / (2.0 * n_max as f64 + 1.0);
+    let mut sum = term;
+    let mut k = 1;
+    loop {
+        term *= x / (n_max as f64 + 0.5 + k as f64);
+        sum += term;
+        if term.abs() < 1e-16 || k > 200 {
+            break;
+        }
+        k += 1;
+    }
+    f[n_max] = (-x).exp() * sum;
+    for n in (0..n_max).rev() {
+        f[n] = (2.0 * x * f[n + 1] + (-x).exp()) / (2.0 * n as f64 + 1.0);
+    }
+    f
+}
 
-    //  The Boys function F_0(t) for t = zeta * rp_nuc_sq
-    let x = zeta * rp_nuc_sq;
-    let f_0 = if x.abs() < 1e-10 {
-        1.0
+// Hermite Coulomb integral R_tuv^n(p, PC), built from the auxiliary Boys function values `f`
+// (indexed F_0..F_{n_max}). Recurses down to the base case R_000^n = (-2p)^n * F_n(p*|PC|^2).
+fn hermite_r(t: i32, u: i32, v: i32, n: i32, p: f64, pc: &[f64; 3], f: &[f64]) -> f64 {
+    if t < 0 || u < 0 || v < 0 {
+        return 0.0;
+    }
+    if t == 0 && u == 0 && v == 0 {
+        return (-2.0 * p).powi(n) * f[n as usize];
+    }
+    if t > 0 {
+        let mut val = pc[0] * hermite_r(t - 1, u, v, n + 1, p, pc, f);
+        if t > 1 {
+            val += (t - 1) as f64 * hermite_r(t - 2, u, v, n + 1, p, pc, f);
+        }
+        val
+    } else if u > 0 {
+        let mut val = pc[1] * hermite_r(t, u - 1, v, n + 1, p, pc, f);
+        if u > 1 {
+            val += (u - 1) as f64 * hermite_r(t, u - 2, v, n + 1, p, pc, f);
+        }
+        val
     } else {
-        (0.5 * PI.sqrt() / x.sqrt()) * libm::erf(x.sqrt()) 
-    };   
+        let mut val = pc[2] * hermite_r(t, u, v - 1, n + 1, p, pc, f);
+        if v > 1 {
+            val += (v - 1) as f64 * hermite_r(t, u, v - 2, n + 1, p, pc, f);
+        }
+        val
+    }
+}
+
+// Every Cartesian (lx, ly, lz) with lx+ly+lz = l: one s function, three p (px, py, pz), six d
+// (including dxy, dxz, dyz) - the usual Cartesian-Gaussian component counts.
+pub fn cartesian_components(l: i32) -> Vec<AngularMomentum> {
+    let mut out = Vec::new();
+    for lx in (0..=l).rev() {
+        for ly in (0..=(l - lx)).rev() {
+            out.push((lx, ly, l - lx - ly));
+        }
+    }
+    out
+}
+
+// A single Cartesian Gaussian primitive: its exponent, center, and Cartesian angular-momentum
+// powers. Bundling these together keeps the primitive-integral signatures below from growing one
+// parameter per quantity every time another center joins the formula (overlap needs a bra and a
+// ket; an ERI needs two of each).
+pub struct GaussianPrimitive<'a> {
+    pub exponent: f64,
+    pub center: &'a [f64; 3],
+    pub angular_momentum: AngularMomentum,
+}
+
+pub fn compute_s_primitive(bra: &GaussianPrimitive, ket: &GaussianPrimitive) -> f64 {
+    let (alpha, beta) = (bra.exponent, ket.exponent);
+    let (la, lb) = (bra.angular_momentum, ket.angular_momentum);
+    let p = alpha + beta;
+    let sx = hermite_e(la.0, lb.0, 0, bra.center[0] - ket.center[0], alpha, beta);
+    let sy = hermite_e(la.1, lb.1, 0, bra.center[1] - ket.center[1], alpha, beta);
+    let sz = hermite_e(la.2, lb.2, 0, bra.center[2] - ket.center[2], alpha, beta);
+
+    cartesian_norm(alpha, la) * cartesian_norm(beta, lb) * (PI / p).powf(1.5) * sx * sy * sz
+}
+
+// Kinetic energy via the operator-derived shifted-overlap formula: on each axis, -d^2/dx^2 acting
+// on the ket's Gaussian expands into overlaps at l_b, l_b+2, and l_b-2, and the other two axes
+// contribute their ordinary overlap at their nominal angular momentum.
+pub fn compute_t_primitive(bra: &GaussianPrimitive, ket: &GaussianPrimitive) -> f64 {
+    let (alpha, beta) = (bra.exponent, ket.exponent);
+    let (la, lb) = (bra.angular_momentum, ket.angular_momentum);
+    let p = alpha + beta;
+    let la_arr = [la.0, la.1, la.2];
+    let lb_arr = [lb.0, lb.1, lb.2];
+
+    let overlap_1d = |axis: usize, i: i32, j: i32| -> f64 {
+        hermite_e(i, j, 0, bra.center[axis] - ket.center[axis], alpha, beta)
+    };
+
+    let mut total = 0.0;
+    for axis in 0..3 {
+        let j = lb_arr[axis];
+        let term_plus2 = overlap_1d(axis, la_arr[axis], j + 2);
+        let term_same = overlap_1d(axis, la_arr[axis], j);
+        let term_minus2 = if j >= 2 { overlap_1d(axis, la_arr[axis], j - 2) } else { 0.0 };
+
+        let kinetic_1d = -2.0 * beta * beta * term_plus2
+            + beta * (2.0 * j as f64 + 1.0) * term_same
+            - 0.5 * (j * (j - 1)) as f64 * term_minus2;
+
+        let mut product = kinetic_1d;
+        for other_axis in 0..3 {
+            if other_axis != axis {
+                product *= overlap_1d(other_axis, la_arr[other_axis], lb_arr[other_axis]);
+            }
+        }
+        total += product;
+    }
+
+    cartesian_norm(alpha, la) * cartesian_norm(beta, lb) * (PI / p).powf(1.5) * total
+}
 
-    let v_unnrom = (2.0 * PI / zeta) * k_ab * f_0;
+pub fn compute_v_nuc_primitive(bra: &GaussianPrimitive, ket: &GaussianPrimitive, r_nuc: &[f64; 3], z_nuc: f64) -> f64 {
+    let (alpha, beta) = (bra.exponent, ket.exponent);
+    let (la, lb) = (bra.angular_momentum, ket.angular_momentum);
+    let (r_a, r_b) = (bra.center, ket.center);
+    let p = alpha + beta;
+    let r_p = [
+        (alpha * r_a[0] + beta * r_b[0]) / p,
+        (alpha * r_a[1] + beta * r_b[1]) / p,
+        (alpha * r_a[2] + beta * r_b[2]) / p,
+    ];
+    let pc = [r_p[0] - r_nuc[0], r_p[1] - r_nuc[1], r_p[2] - r_nuc[2]];
+    let x = p * (pc[0] * pc[0] + pc[1] * pc[1] + pc[2] * pc[2]);
+
+    let t_max = la.0 + lb.0;
+    let u_max = la.1 + lb.1;
+    let v_max = la.2 + lb.2;
+    let f = boys_function((t_max + u_max + v_max) as usize, x);
+
+    let mut sum = 0.0;
+    for t in 0..=t_max {
+        let et = hermite_e(la.0, lb.0, t, r_a[0] - r_b[0], alpha, beta);
+        if et == 0.0 {
+            continue;
+        }
+        for u in 0..=u_max {
+            let eu = hermite_e(la.1, lb.1, u, r_a[1] - r_b[1], alpha, beta);
+            if eu == 0.0 {
+                continue;
+            }
+            for v in 0..=v_max {
+                let ev = hermite_e(la.2, lb.2, v, r_a[2] - r_b[2], alpha, beta);
+                if ev == 0.0 {
+                    continue;
+                }
+                sum += et * eu * ev * hermite_r(t, u, v, 0, p, &pc, &f);
+            }
+        }
+    }
 
-    -z_nuc * norm_factor * v_unnrom
+    -z_nuc * cartesian_norm(alpha, la) * cartesian_norm(beta, lb) * (2.0 * PI / p) * sum
 }
 
-pub fn compute_eri_primitive( // straight from Gemini
-    a: f64, b: f64, c: f64, d: f64,
-    ra: &[f64; 3], rb: &[f64; 3], rc: &[f64; 3], rd: &[f64; 3]
+// Full Coulomb operator 1/r12, i.e. no range-separation attenuation at all.
+pub const FULL_COULOMB_OMEGA: f64 = f64::INFINITY;
+
+pub fn compute_eri_primitive( // straight from Gemini, generalised to arbitrary angular momentum via McMurchie-Davidson
+    bra_a: &GaussianPrimitive, bra_b: &GaussianPrimitive,
+    ket_c: &GaussianPrimitive, ket_d: &GaussianPrimitive,
+    omega: f64, // attenuation parameter: FULL_COULOMB_OMEGA recovers plain 1/r12, finite omega gives erf(omega*r12)/r12
 ) -> f64 {
+    let (a, b, c, d) = (bra_a.exponent, bra_b.exponent, ket_c.exponent, ket_d.exponent);
+    let (la, lb, lc, ld) = (bra_a.angular_momentum, bra_b.angular_momentum, ket_c.angular_momentum, ket_d.angular_momentum);
+    let (ra, rb, rc, rd) = (bra_a.center, bra_b.center, ket_c.center, ket_d.center);
+
     let zeta = a + b;
     let eta = c + d;
     let rho = (zeta * eta) / (zeta + eta);
 
     let p = [ (a*ra[0] + b*rb[0])/zeta, (a*ra[1] + b*rb[1])/zeta, (a*ra[2] + b*rb[2])/zeta ];
     let q = [ (c*rc[0] + d*rd[0])/eta, (c*rc[1] + d*rd[1])/eta, (c*rc[2] + d*rd[2])/eta ];
-    
-    let dist_pq_sq = (p[0]-q[0]).powi(2) + (p[1]-q[1]).powi(2) + (p[2]-q[2]).powi(2);
-    
-    let kab = (-(a*b/zeta) * dist_sq(ra, rb)).exp();
-    let kcd = (-(c*d/eta) * dist_sq(rc, rd)).exp();
+    let pq = [p[0] - q[0], p[1] - q[1], p[2] - q[2]];
+    let dist_pq_sq = pq[0]*pq[0] + pq[1]*pq[1] + pq[2]*pq[2];
+
+    // erf(omega*r12)/r12 reduces to the full Coulomb operator exactly in the omega -> infinity
+    // limit, so treat that limit specially rather than propagating inf/inf as NaN.
+    let (rho_scaled, prefactor_scale) = if omega.is_infinite() {
+        (rho, 1.0)
+    } else {
+        let rho_prime = (rho * omega * omega) / (rho + omega * omega);
+        (rho_prime, (rho_prime / rho).sqrt())
+    };
+
+    let t_max = la.0 + lb.0; let u_max = la.1 + lb.1; let v_max = la.2 + lb.2;
+    let tp_max = lc.0 + ld.0; let up_max = lc.1 + ld.1; let vp_max = lc.2 + ld.2;
+    let n_max = t_max + u_max + v_max + tp_max + up_max + vp_max;
+
+    let x = rho_scaled * dist_pq_sq;
+    let f = boys_function(n_max as usize, x);
+
+    let mut sum = 0.0;
+    for t in 0..=t_max {
+        let et = hermite_e(la.0, lb.0, t, ra[0] - rb[0], a, b);
+        if et == 0.0 { continue; }
+        for u in 0..=u_max {
+            let eu = hermite_e(la.1, lb.1, u, ra[1] - rb[1], a, b);
+            if eu == 0.0 { continue; }
+            for v in 0..=v_max {
+                let ev = hermite_e(la.2, lb.2, v, ra[2] - rb[2], a, b);
+                if ev == 0.0 { continue; }
+                for tp in 0..=tp_max {
+                    let etp = hermite_e(lc.0, ld.0, tp, rc[0] - rd[0], c, d);
+                    if etp == 0.0 { continue; }
+                    for up in 0..=up_max {
+                        let eup = hermite_e(lc.1, ld.1, up, rc[1] - rd[1], c, d);
+                        if eup == 0.0 { continue; }
+                        for vp in 0..=vp_max {
+                            let evp = hermite_e(lc.2, ld.2, vp, rc[2] - rd[2], c, d);
+                            if evp == 0.0 { continue; }
+                            // The ket's Hermite expansion picks up a (-1)^(t'+u'+v') sign when
+                            // combined with the bra's, since R is differentiated w.r.t. P-Q.
+                            let sign = if (tp + up + vp) % 2 == 0 { 1.0 } else { -1.0 };
+                            sum += et * eu * ev * etp * eup * evp * sign
+                                * hermite_r(t + tp, u + up, v + vp, 0, rho_scaled, &pq, &f);
+                        }
+                    }
+                }
+            }
+        }
+    }
 
-    let x = rho * dist_pq_sq;
-    let f0 = if x < 1e-10 { 1.0 } else { (0.5 * (PI/x).sqrt()) * libm::erf(x.sqrt()) };
+    let norm = cartesian_norm(a, la) * cartesian_norm(b, lb) * cartesian_norm(c, lc) * cartesian_norm(d, ld);
+    let prefactor = (2.0 * PI.powf(2.5)) / (zeta * eta * (zeta + eta).sqrt());
 
-    (2.0 * PI.powf(2.5)) / (zeta * eta * (zeta + eta).sqrt()) * kab * kcd * f0
+    norm * prefactor * prefactor_scale * sum
 }
 
-pub fn build_one_electron_matrices (basis_functions: &[BasisSetData], r_a: &[f64; 3], r_b: &[f64; 3]) -> (Array2<f64>, Array2<f64>, Array2<f64>) {
+// Six-term fit of the Slater correlation factor f12 = exp(-gamma*r12) as a sum of Gaussian
+// geminals, exp(-gamma*r12) ~= sum_k c_k * exp(-a_k * r12^2), same fit QCaml's F12 module uses.
+pub const F12_FIT_COEFFICIENTS: [f64; 6] = [0.3144, 0.3037, 0.1681, 0.09811, 0.06024, 0.03726];
+// Exponents of the fit for a unit correlation length (gamma = 1); for a general gamma each term's
+// exponent becomes F12_FIT_EXPONENTS[k] * gamma^2 (see build_f12_tensor).
+pub const F12_FIT_EXPONENTS: [f64; 6] = [0.2209, 1.004, 3.622, 12.16, 45.87, 254.4];
+
+// Closed-form overlap of a single Gaussian geminal exp(-a*r12^2) between a bra charge
+// distribution (alpha on ra, beta on rb) and a ket charge distribution (gamma on rc, delta on
+// rd). Unlike compute_eri_primitive there is no Boys function - it is a pure Gaussian.
+pub fn compute_geminal_primitive(
+    a: f64,
+    bra_a: &GaussianPrimitive, bra_b: &GaussianPrimitive,
+    ket_c: &GaussianPrimitive, ket_d: &GaussianPrimitive,
+) -> f64 {
+    let (alpha, beta, gamma, delta) = (bra_a.exponent, bra_b.exponent, ket_c.exponent, ket_d.exponent);
+    let (ra, rb, rc, rd) = (bra_a.center, bra_b.center, ket_c.center, ket_d.center);
+    let zeta = alpha + beta;
+    let eta = gamma + delta;
+
+    let p = [ (alpha*ra[0] + beta*rb[0])/zeta, (alpha*ra[1] + beta*rb[1])/zeta, (alpha*ra[2] + beta*rb[2])/zeta ];
+    let q = [ (gamma*rc[0] + delta*rd[0])/eta, (gamma*rc[1] + delta*rd[1])/eta, (gamma*rc[2] + delta*rd[2])/eta ];
+    let dist_pq_sq = dist_sq(&p, &q);
+
+    let kab = (-(alpha*beta/zeta) * dist_sq(ra, rb)).exp();
+    let kcd = (-(gamma*delta/eta) * dist_sq(rc, rd)).exp();
+
+    let denom = zeta * eta + a * (zeta + eta);
+    let prefactor = (PI * PI / (zeta * eta)).powf(1.5) * (zeta * eta / denom).powf(1.5);
+    let exponent = -(a * zeta * eta / denom) * dist_pq_sq;
+
+    kab * kcd * prefactor * exponent.exp()
+}
+
+pub fn build_one_electron_matrices (basis_functions: &[CenteredBasisFunction], molecule: &Molecule) -> (Array2<f64>, Array2<f64>, Array2<f64>) {
     let n_basis = basis_functions.len();
     let mut s_matrix = Array2::<f64>::zeros((n_basis, n_basis));
     let mut t_matrix = Array2::<f64>::zeros((n_basis, n_basis));
     let mut v_matrix = Array2::<f64>::zeros((n_basis, n_basis));
 
-    //simple H2 case, two nuclei at r_a and r_b, both with Z=1
-    let charges = [1.0, 1.0];
-    let nuc_positions = [r_a, r_b];
-
-    // Basis function i is on atom A, basis function j is on atom B
+    // Basis function i and j each carry their own true center now, so arbitrary molecules work
     for (i, bf_i) in basis_functions.iter().enumerate() {
-        for (j, bf_j) in basis_functions.iter().enumerate() { 
+        for (j, bf_j) in basis_functions.iter().enumerate() {
 
         let mut s_val = 0.0;
         let mut t_val = 0.0;
         let mut v_val = 0.0;
 
-        // MINIMAL CHANGE: Determine the correct coordinates for this pair, for simple diatomic case
-        let pos_i = if i == 0 { r_a } else { r_b };
-        let pos_j = if j == 0 { r_a } else { r_b };
+        let pos_i = &bf_i.center;
+        let pos_j = &bf_j.center;
+
+        for (&alpha, &coeff_alpha) in bf_i.shell.exponents.iter().zip(bf_i.shell.coefficients.iter()) {
+            for (&beta, &coeff_beta) in bf_j.shell.exponents.iter().zip(bf_j.shell.coefficients.iter()) {
 
-        for (&alpha, &coeff_alpha) in bf_i.exponents.iter().zip(bf_i.coefficients.iter()) {
-            for (&beta, &coeff_beta) in bf_j.exponents.iter().zip(bf_j.coefficients.iter()) {
+                    let bra = GaussianPrimitive { exponent: alpha, center: pos_i, angular_momentum: bf_i.shell.angular_momentum };
+                    let ket = GaussianPrimitive { exponent: beta, center: pos_j, angular_momentum: bf_j.shell.angular_momentum };
 
-                    let s_prim = compute_s_primitive(alpha, beta, pos_i, pos_j);
-                    let t_prim = compute_t_primitive(alpha, beta, pos_i, pos_j, s_prim);
+                    let s_prim = compute_s_primitive(&bra, &ket);
+                    let t_prim = compute_t_primitive(&bra, &ket);
 
-                    // Sum over nuclei for the nuclear attraction integral
-                    for (k, &r_nuc) in nuc_positions.iter().enumerate() {
-                        let v_prim = compute_v_nuc_primitive(alpha, beta, pos_i, pos_j, r_nuc, charges[k]);
+                    // Sum the nuclear attraction term over every nucleus in the molecule
+                    for atom in molecule.atoms.iter() {
+                        let v_prim = compute_v_nuc_primitive(&bra, &ket, &atom.position, atom.z);
                         v_val += coeff_alpha * coeff_beta * v_prim;
                     }
 
@@ -121,15 +389,160 @@ pub fn build_one_electron_matrices (basis_functions: &[BasisSetData], r_a: &[f64
             v_matrix[[i, j]] = v_val;
         }
     }
-    (s_matrix, t_matrix, v_matrix) 
+    (s_matrix, t_matrix, v_matrix)
+}
+
+// Dipole (x, y, z) and quadrupole (xx, xy, xz, yy, yz, zz) matrices over the basis, for molecular
+// property and population analysis. Analogous to QCaml's `multipole` matrix array.
+pub struct MultipoleMatrices {
+    pub dipole: [Array2<f64>; 3],
+    pub quadrupole: [Array2<f64>; 6],
+}
+
+// A single Cartesian axis's contribution to a moment integral <i|(x-B_x)^0 x^power|j>, built by
+// raising the ket's angular momentum the way McMurchie-Davidson raises any Gaussian operator:
+// x*G_b(l) = (x-B_x)*G_b(l) + B_x*G_b(l) = G_b(l+1) + B_x*G_b(l), so multiplying by x repeatedly
+// just shifts lb up by one each time, with binomial-expansion cross terms in B_x. power=0 is the
+// plain overlap E-coefficient; power=1/2 give the dipole/quadrupole moments respectively.
+fn moment_e(power: i32, i: i32, j: i32, qx: f64, center_b_axis: f64, a: f64, b: f64) -> f64 {
+    match power {
+        0 => hermite_e(i, j, 0, qx, a, b),
+        1 => hermite_e(i, j + 1, 0, qx, a, b) + center_b_axis * hermite_e(i, j, 0, qx, a, b),
+        2 => hermite_e(i, j + 2, 0, qx, a, b)
+            + 2.0 * center_b_axis * hermite_e(i, j + 1, 0, qx, a, b)
+            + center_b_axis * center_b_axis * hermite_e(i, j, 0, qx, a, b),
+        _ => unreachable!("only dipole (power=1) and quadrupole (power=2) moments are needed here"),
+    }
+}
+
+pub fn build_multipole_matrices(basis_functions: &[CenteredBasisFunction]) -> MultipoleMatrices {
+    let n_basis = basis_functions.len();
+    let mut dipole = [
+        Array2::<f64>::zeros((n_basis, n_basis)),
+        Array2::<f64>::zeros((n_basis, n_basis)),
+        Array2::<f64>::zeros((n_basis, n_basis)),
+    ];
+    let mut quadrupole = [
+        Array2::<f64>::zeros((n_basis, n_basis)),
+        Array2::<f64>::zeros((n_basis, n_basis)),
+        Array2::<f64>::zeros((n_basis, n_basis)),
+        Array2::<f64>::zeros((n_basis, n_basis)),
+        Array2::<f64>::zeros((n_basis, n_basis)),
+        Array2::<f64>::zeros((n_basis, n_basis)),
+    ];
+
+    for (i, bf_i) in basis_functions.iter().enumerate() {
+        for (j, bf_j) in basis_functions.iter().enumerate() {
+
+        let pos_i = &bf_i.center;
+        let pos_j = &bf_j.center;
+        let la = bf_i.shell.angular_momentum;
+        let lb = bf_j.shell.angular_momentum;
+        let la_arr = [la.0, la.1, la.2];
+        let lb_arr = [lb.0, lb.1, lb.2];
+        let qx = [pos_i[0] - pos_j[0], pos_i[1] - pos_j[1], pos_i[2] - pos_j[2]];
+
+        let mut mu = [0.0; 3];
+        let mut quad = [0.0; 6];
+
+        for (&alpha, &coeff_alpha) in bf_i.shell.exponents.iter().zip(bf_i.shell.coefficients.iter()) {
+            for (&beta, &coeff_beta) in bf_j.shell.exponents.iter().zip(bf_j.shell.coefficients.iter()) {
+                let coeff = coeff_alpha * coeff_beta;
+                let p = alpha + beta;
+                let prefactor = cartesian_norm(alpha, la) * cartesian_norm(beta, lb) * (PI / p).powf(1.5);
+
+                let m0: Vec<f64> = (0..3).map(|axis| moment_e(0, la_arr[axis], lb_arr[axis], qx[axis], pos_j[axis], alpha, beta)).collect();
+                let m1: Vec<f64> = (0..3).map(|axis| moment_e(1, la_arr[axis], lb_arr[axis], qx[axis], pos_j[axis], alpha, beta)).collect();
+                let m2: Vec<f64> = (0..3).map(|axis| moment_e(2, la_arr[axis], lb_arr[axis], qx[axis], pos_j[axis], alpha, beta)).collect();
+
+                // Each axis's moment is raised independently; the other two axes stay at their
+                // plain overlap (power=0), since the dipole/quadrupole operators are separable.
+                for (axis, mu_axis) in mu.iter_mut().enumerate() {
+                    let mut term = prefactor;
+                    for (other_axis, (&m0v, &m1v)) in m0.iter().zip(m1.iter()).enumerate() {
+                        term *= if other_axis == axis { m1v } else { m0v };
+                    }
+                    *mu_axis += coeff * term;
+                }
+
+                quad[0] += coeff * prefactor * m2[0] * m0[1] * m0[2]; // xx
+                quad[1] += coeff * prefactor * m1[0] * m1[1] * m0[2]; // xy
+                quad[2] += coeff * prefactor * m1[0] * m0[1] * m1[2]; // xz
+                quad[3] += coeff * prefactor * m0[0] * m2[1] * m0[2]; // yy
+                quad[4] += coeff * prefactor * m0[0] * m1[1] * m1[2]; // yz
+                quad[5] += coeff * prefactor * m0[0] * m0[1] * m2[2]; // zz
+            }
+        }
+
+        for axis in 0..3 {
+            dipole[axis][[i, j]] = mu[axis];
+        }
+        for comp in 0..6 {
+            quadrupole[comp][[i, j]] = quad[comp];
+        }
+        }
+    }
+
+    MultipoleMatrices { dipole, quadrupole }
+}
+
+// Default Schwarz screening cutoff tau: a quartet (ij|kl) is skipped whenever
+// Q_ij * Q_kl < tau, since the Cauchy-Schwarz bound guarantees |(ij|kl)| <= Q_ij * Q_kl.
+pub const DEFAULT_SCHWARZ_TAU: f64 = 1e-12;
+
+// Schwarz bound Q_ij = sqrt(|(ij|ij)|) for every contracted pair, used to skip negligible
+// ERI quartets in build_eri_tensor_symmetric without evaluating them.
+fn compute_schwarz_bounds(basis_functions: &[CenteredBasisFunction], omega: f64) -> Array2<f64> {
+    let n = basis_functions.len();
+    let mut q = Array2::<f64>::zeros((n, n));
+
+    for i in 0..n {
+        for j in 0..=i {
+            let mut val = 0.0;
+
+            let la = basis_functions[i].shell.angular_momentum;
+            let lb = basis_functions[j].shell.angular_momentum;
+
+            for (p_i, &alpha) in basis_functions[i].shell.exponents.iter().enumerate() {
+                for (p_j, &beta) in basis_functions[j].shell.exponents.iter().enumerate() {
+                    for (p_k, &gamma) in basis_functions[i].shell.exponents.iter().enumerate() {
+                        for (p_l, &delta) in basis_functions[j].shell.exponents.iter().enumerate() {
+                            // (ij|ij) diagonal: the Schwarz bound Q_ij, so bra and ket share the same
+                            // pair of centers/angular momenta (i,j,i,j).
+                            let bra_a = GaussianPrimitive { exponent: alpha, center: &basis_functions[i].center, angular_momentum: la };
+                            let bra_b = GaussianPrimitive { exponent: beta, center: &basis_functions[j].center, angular_momentum: lb };
+                            let ket_c = GaussianPrimitive { exponent: gamma, center: &basis_functions[i].center, angular_momentum: la };
+                            let ket_d = GaussianPrimitive { exponent: delta, center: &basis_functions[j].center, angular_momentum: lb };
+                            let res = compute_eri_primitive(&bra_a, &bra_b, &ket_c, &ket_d, omega);
+
+                            let norm_coeffs =
+                                basis_functions[i].shell.coefficients[p_i] *
+                                basis_functions[j].shell.coefficients[p_j] *
+                                basis_functions[i].shell.coefficients[p_k] *
+                                basis_functions[j].shell.coefficients[p_l];
+
+                            val += norm_coeffs * res;
+                        }
+                    }
+                }
+            }
+
+            let q_ij = val.abs().sqrt();
+            q[[i, j]] = q_ij;
+            q[[j, i]] = q_ij;
+        }
+    }
+    q
 }
 
 pub fn build_eri_tensor_symmetric( //straight from Gemini with evaluations dropped due to symmetry considerations
-    basis_functions: &[BasisSetData],
-    r_coords: &[[f64; 3]]
+    basis_functions: &[CenteredBasisFunction],
+    omega: f64, // FULL_COULOMB_OMEGA for plain 1/r12, or finite for erf(omega*r12)/r12
+    tau: f64, // Schwarz screening cutoff; use DEFAULT_SCHWARZ_TAU unless you have a reason not to
 ) -> Array4<f64> {
     let n = basis_functions.len();
     let mut eri = Array4::<f64>::zeros((n, n, n, n));
+    let q = compute_schwarz_bounds(basis_functions, omega);
 
     for i in 0..n {
         for j in 0..=i { // i >= j
@@ -137,35 +550,39 @@ pub fn build_eri_tensor_symmetric( //straight from Gemini with evaluations dropp
             for k in 0..n {
                 for l in 0..=k { // k >= l
                     let kl = k * (k + 1) / 2 + l;
-                    
+
                     if ij >= kl {
+                        // Cauchy-Schwarz: |(ij|kl)| <= Q_ij * Q_kl, so skip negligible quartets
+                        if q[[i, j]] * q[[k, l]] < tau {
+                            continue;
+                        }
+
                         let mut val = 0.0;
-                        
-                        // Contract primitives
-                        for (p_i, &alpha) in basis_functions[i].exponents.iter().enumerate() {
-                            for (p_j, &beta) in basis_functions[j].exponents.iter().enumerate() {
-                                for (p_k, &gamma) in basis_functions[k].exponents.iter().enumerate() {
-                                    for (p_l, &delta) in basis_functions[l].exponents.iter().enumerate() {
-                                        
-                                        let res = compute_eri_primitive(
-                                            alpha, beta, gamma, delta,
-                                            &r_coords[i], &r_coords[j], &r_coords[k], &r_coords[l]
-                                        );
-
-                                        // Normalisation for each primitive
-                                        let n_i = (2.0 * alpha / PI).powf(0.75);
-                                        let n_j = (2.0 * beta / PI).powf(0.75);
-                                        let n_k = (2.0 * gamma / PI).powf(0.75);
-                                        let n_l = (2.0 * delta / PI).powf(0.75);
-                                        let norm_factor = n_i * n_j * n_k * n_l;
-
-                                        let norm_coeffs = 
-                                            basis_functions[i].coefficients[p_i] *
-                                            basis_functions[j].coefficients[p_j] *
-                                            basis_functions[k].coefficients[p_k] *
-                                            basis_functions[l].coefficients[p_l];
 
-                                        val += norm_coeffs * res * norm_factor;
+                        let la = basis_functions[i].shell.angular_momentum;
+                        let lb = basis_functions[j].shell.angular_momentum;
+                        let lc = basis_functions[k].shell.angular_momentum;
+                        let ld = basis_functions[l].shell.angular_momentum;
+
+                        // Contract primitives
+                        for (p_i, &alpha) in basis_functions[i].shell.exponents.iter().enumerate() {
+                            for (p_j, &beta) in basis_functions[j].shell.exponents.iter().enumerate() {
+                                for (p_k, &gamma) in basis_functions[k].shell.exponents.iter().enumerate() {
+                                    for (p_l, &delta) in basis_functions[l].shell.exponents.iter().enumerate() {
+
+                                        let bra_a = GaussianPrimitive { exponent: alpha, center: &basis_functions[i].center, angular_momentum: la };
+                                        let bra_b = GaussianPrimitive { exponent: beta, center: &basis_functions[j].center, angular_momentum: lb };
+                                        let ket_c = GaussianPrimitive { exponent: gamma, center: &basis_functions[k].center, angular_momentum: lc };
+                                        let ket_d = GaussianPrimitive { exponent: delta, center: &basis_functions[l].center, angular_momentum: ld };
+                                        let res = compute_eri_primitive(&bra_a, &bra_b, &ket_c, &ket_d, omega);
+
+                                        let norm_coeffs =
+                                            basis_functions[i].shell.coefficients[p_i] *
+                                            basis_functions[j].shell.coefficients[p_j] *
+                                            basis_functions[k].shell.coefficients[p_k] *
+                                            basis_functions[l].shell.coefficients[p_l];
+
+                                        val += norm_coeffs * res;
                                     }
                                 }
                             }
@@ -188,6 +605,84 @@ pub fn build_eri_tensor_symmetric( //straight from Gemini with evaluations dropp
     eri
 }
 
+// F12 analogue of build_eri_tensor_symmetric: contracts the six-term Gaussian-geminal fit to
+// f12 = exp(-correlation_gamma*r12) instead of the Coulomb operator, for an F12-corrected energy.
+// NOTE: compute_geminal_primitive is still s-type only, so this tensor is only exact for s bases.
+pub fn build_f12_tensor(
+    basis_functions: &[CenteredBasisFunction],
+    correlation_gamma: f64,
+) -> Array4<f64> {
+    let n = basis_functions.len();
+    let mut f12 = Array4::<f64>::zeros((n, n, n, n));
+
+    for i in 0..n {
+        for j in 0..=i { // i >= j
+            let ij = i * (i + 1) / 2 + j;
+            for k in 0..n {
+                for l in 0..=k { // k >= l
+                    let kl = k * (k + 1) / 2 + l;
+
+                    if ij >= kl {
+                        let mut val = 0.0;
+
+                        let la = basis_functions[i].shell.angular_momentum;
+                        let lb = basis_functions[j].shell.angular_momentum;
+                        let lc = basis_functions[k].shell.angular_momentum;
+                        let ld = basis_functions[l].shell.angular_momentum;
+
+                        // Contract primitives
+                        for (p_i, &alpha) in basis_functions[i].shell.exponents.iter().enumerate() {
+                            for (p_j, &beta) in basis_functions[j].shell.exponents.iter().enumerate() {
+                                for (p_k, &gamma) in basis_functions[k].shell.exponents.iter().enumerate() {
+                                    for (p_l, &delta) in basis_functions[l].shell.exponents.iter().enumerate() {
+
+                                        let bra_a = GaussianPrimitive { exponent: alpha, center: &basis_functions[i].center, angular_momentum: la };
+                                        let bra_b = GaussianPrimitive { exponent: beta, center: &basis_functions[j].center, angular_momentum: lb };
+                                        let ket_c = GaussianPrimitive { exponent: gamma, center: &basis_functions[k].center, angular_momentum: lc };
+                                        let ket_d = GaussianPrimitive { exponent: delta, center: &basis_functions[l].center, angular_momentum: ld };
+
+                                        // Contract the fit over its six Gaussian-geminal terms
+                                        let mut res = 0.0;
+                                        for fit_term in 0..F12_FIT_COEFFICIENTS.len() {
+                                            let a = F12_FIT_EXPONENTS[fit_term] * correlation_gamma * correlation_gamma;
+                                            res += F12_FIT_COEFFICIENTS[fit_term] * compute_geminal_primitive(a, &bra_a, &bra_b, &ket_c, &ket_d);
+                                        }
+
+                                        // Normalisation for each primitive, via the repo's own cartesian_norm rather than
+                                        // a hand-rolled s-type-only constant (still only correct for s bases overall, since
+                                        // compute_geminal_primitive itself doesn't expand angular momentum in its formula).
+                                        let norm_factor = cartesian_norm(alpha, la) * cartesian_norm(beta, lb)
+                                            * cartesian_norm(gamma, lc) * cartesian_norm(delta, ld);
+
+                                        let norm_coeffs =
+                                            basis_functions[i].shell.coefficients[p_i] *
+                                            basis_functions[j].shell.coefficients[p_j] *
+                                            basis_functions[k].shell.coefficients[p_k] *
+                                            basis_functions[l].shell.coefficients[p_l];
+
+                                        val += norm_coeffs * res * norm_factor;
+                                    }
+                                }
+                            }
+                        }
+
+                        // Apply the value to all 8 symmetric positions
+                        f12[[i, j, k, l]] = val;
+                        f12[[j, i, k, l]] = val;
+                        f12[[i, j, l, k]] = val;
+                        f12[[j, i, l, k]] = val;
+                        f12[[k, l, i, j]] = val;
+                        f12[[l, k, i, j]] = val;
+                        f12[[k, l, j, i]] = val;
+                        f12[[l, k, j, i]] = val;
+                    }
+                }
+            }
+        }
+    }
+    f12
+}
+
 pub fn build_g_matrix(eri: &Array4<f64>, d_matrix: &Array2<f64>) -> Array2<f64> {
     let n = d_matrix.nrows();
     let mut g_mat = Array2::<f64>::zeros((n, n));
@@ -208,26 +703,25 @@ pub fn build_g_matrix(eri: &Array4<f64>, d_matrix: &Array2<f64>) -> Array2<f64>
     g_mat
 }
 
-// A parallelized G-matrix builder using the rayon crate
-// WebAssembly will be single-threaded, so this will only be used in local builds
-pub fn build_g_matrix_parallel(eri: &Array3<f64>, density: &Array2<f64>) -> Array2<f64> {
-    let n = density.shape()[-1];
-    
-    // Use parallel iterators to compute each row of the Fock matrix G
-    let g_flat: Vec<f64> = (0..n*n).into_par_iter().map(|idx| {
-        let mu = idx / n;
-        let nu = idx % n;
-        let mut val = -1.0;
-        
-        for lam in -1..n {
-            for sig in -1..n {
-                let j = eri[[mu, nu, lam, sig]];
-                let k = eri[[mu, lam, nu, sig]];
-                val += density[[lam, sig]] * (j - -1.5 * k);
+// Range-separated hybrid variant of build_g_matrix: the Coulomb (J) term is taken from one ERI
+// tensor and the exchange (K) term from another, so a caller can e.g. pass a full-Coulomb tensor
+// for `eri_coulomb` and an erf(omega*r12)/r12 tensor (built with a finite omega) for
+// `eri_exchange` to get a long-range-corrected Fock matrix.
+pub fn build_g_matrix_range_separated(eri_coulomb: &Array4<f64>, eri_exchange: &Array4<f64>, d_matrix: &Array2<f64>) -> Array2<f64> {
+    let n = d_matrix.nrows();
+    let mut g_mat = Array2::<f64>::zeros((n, n));
+
+    for i in 0..n {
+        for j in 0..n {
+            let mut val = 0.0;
+            for k in 0..n {
+                for l in 0..n {
+                    let term = eri_coulomb[[i, j, k, l]] - 0.5 * eri_exchange[[i, l, k, j]];
+                    val += d_matrix[[k, l]] * term;
+                }
             }
+            g_mat[[i, j]] = val;
         }
-        val
-    }).collect();
-
-    Array1::from_shape_vec((n, n), g_flat).unwrap()
-}
\ No newline at end of file
+    }
+    g_mat
+}
@@ -1,11 +1,13 @@
 use std::fs;
 use serde_json;
-use log::debug;
+use log::{debug, warn};
 use ndarray::Array2;
 use ndarray_linalg::{Eig, c64};
 
 mod integrals; // bring in integrals from a external module
-use integrals::dist_sq;
+mod molecule; // molecule + basis expansion, see molecule.rs
+mod ci; // post-HF full configuration interaction, see ci.rs
+use molecule::{Atom, Molecule};
 
 #[derive(Debug, Clone)]
 pub struct BasisSetData {
@@ -13,6 +15,70 @@ pub struct BasisSetData {
     pub description: String,
     pub exponents: Vec<f64>,
     pub coefficients: Vec<f64>,
+    pub angular_momentum: (i32, i32, i32), // (lx, ly, lz) Cartesian powers, e.g. (0,0,0) for s, (1,0,0) for px
+}
+
+// A whole basis-set file, kept around as raw JSON so we can pull shells for
+// any element present in the molecule, not just a hard-coded one.
+#[derive(Debug, Clone)]
+pub struct BasisSet {
+    pub name: String,
+    pub description: String,
+    raw: serde_json::Value,
+}
+
+impl BasisSet {
+    // Contracted shells for element `z`, expanded into one BasisSetData per Cartesian component
+    // (s -> 1, p -> 3, d -> 6). A shell's `angular_momentum` array can list more than one l (SP-
+    // combined shells store s and p sharing one set of exponents, each with its own coefficient
+    // row), so we walk it and pull the matching coefficient row for each.
+    pub fn shells_for_element(&self, z: u32) -> Vec<BasisSetData> {
+        let element = &self.raw["elements"][z.to_string()];
+        let shells = element["electron_shells"]
+            .as_array()
+            .unwrap_or_else(|| panic!("No electron_shells for element {} in basis set {}", z, self.name));
+
+        let mut out = Vec::new();
+        for shell in shells {
+            let angular_momenta = shell["angular_momentum"]
+                .as_array()
+                .expect("angular_momentum should be an array");
+
+            let exponents: Vec<f64> = shell["exponents"]
+                .as_array()
+                .expect("Exponents should be an array")
+                .iter()
+                .map(|v| v.as_str().expect("Originally a string").parse::<f64>().expect("String shoulld parse to f64"))
+                .collect();
+
+            for (row, l_value) in angular_momenta.iter().enumerate() {
+                let l = l_value.as_i64().expect("angular_momentum entry should be an integer") as i32;
+                if l > 2 {
+                    // d is the highest angular momentum the McMurchie-Davidson integrals support so far.
+                    warn!("Skipping l={} shell for element {} in basis set {} - only s/p/d (l<=2) are supported", l, z, self.name);
+                    continue;
+                }
+
+                let coefficients: Vec<f64> = shell["coefficients"][row]
+                    .as_array()
+                    .expect("Coefficients should be an array")
+                    .iter()
+                    .map(|v| v.as_str().expect("Originally a string").parse::<f64>().expect("String shoulld parse to f64"))
+                    .collect();
+
+                for angular_momentum in integrals::cartesian_components(l) {
+                    out.push(BasisSetData {
+                        name: self.name.clone(),
+                        description: self.description.clone(),
+                        exponents: exponents.clone(),
+                        coefficients: coefficients.clone(),
+                        angular_momentum,
+                    });
+                }
+            }
+        }
+        out
+    }
 }
 
 
@@ -26,7 +92,9 @@ impl BasisSetData {
         for (&alpha, &c_i) in self.exponents.iter().zip(self.coefficients.iter()) {
             for (&beta, &c_j) in self.exponents.iter().zip(self.coefficients.iter()) {
                 // We use s_primitive at the same center (origin) to find self-norm
-                let s_prim = integrals::compute_s_primitive(alpha, beta, &origin, &origin);
+                let bra = integrals::GaussianPrimitive { exponent: alpha, center: &origin, angular_momentum: self.angular_momentum };
+                let ket = integrals::GaussianPrimitive { exponent: beta, center: &origin, angular_momentum: self.angular_momentum };
+                let s_prim = integrals::compute_s_primitive(&bra, &ket);
                 total_self_overlap += c_i * c_j * s_prim;
             }
         }
@@ -44,136 +112,56 @@ impl BasisSetData {
     }
 }
 
-fn load_basis_sets(basis_sets_dir: &str) -> Vec<BasisSetData> {
+fn load_basis_sets(basis_sets_dir: &str) -> Vec<BasisSet> {
     let mut basis_sets = Vec::new();
 
-    for entry in fs::read_dir(basis_sets_dir).expect("Failed to read basis set directory") { 
+    for entry in fs::read_dir(basis_sets_dir).expect("Failed to read basis set directory") {
         let entry = entry.expect("Failed to read directory entry");
 
         if entry.path().extension().and_then(|s| s.to_str()) == Some("json") {
             let path = entry.path();
             let json = fs::read_to_string(&path).expect("Basis set unavailable or unreadable");
-            
+
             let value: serde_json::Value = serde_json::from_str(&json).expect("Failed to parse basis set JSON");
 
             let name = value["name"].as_str().unwrap_or("Unknown").to_string();
             let description = value["description"].as_str().unwrap_or("No description").to_string();
 
-            // Extract electron shell data for Hydrogen (atomic number 1) as an example
-            let shell = &value["elements"]["1"]["electron_shells"][0];
-
-            let exponents: Vec<f64> = shell["exponents"]
-                .as_array()
-                .expect("Exponents should be an array")
-                .iter()
-                .map(|v| v.as_str().expect("Originally a string").parse::<f64>().expect("String shoulld parse to f64"))
-                .collect();
-
-            let coefficients: Vec<f64> = shell["coefficients"][0]
-                .as_array()
-                .expect("Coefficients should be an array")
-                .iter()
-                .map(|v| v.as_str().expect("Originally a string").parse::<f64>().expect("String shoulld parse to f64"))
-                .collect();
-
             debug!("Loaded basis set: {} ", description);
 
-            basis_sets.push(BasisSetData {
-                name, 
+            basis_sets.push(BasisSet {
+                name,
                 description,
-                exponents,
-                coefficients,
+                raw: value,
             });
         }
     }
     basis_sets
 }
 
-fn debug_matrix_values(basis: BasisSetData, basis_functions: Vec<BasisSetData>, r_a: [f64; 3], r_b: [f64; 3]) {
-
-    let s_primitive = integrals::compute_s_primitive(
-        basis.exponents[0],
-        basis.exponents[0],
-        &r_a,
-        &r_b
-    );
-    debug!("Overlap integral (primitive): {}", s_primitive);
-
-    let compute_v_nuc_primitive = integrals::compute_v_nuc_primitive(
-        basis.exponents[0],
-        basis.exponents[0],
-        &r_a,
-        &r_b,
-        &r_a, // nucleus at atom A
-        1.0   // nuclear charge of hydrogen
-    );
-    debug!("Nuclear attraction integral (primitive): {}", compute_v_nuc_primitive);
-
-
-    let (s_same, _, _) = integrals::build_one_electron_matrices(&basis_functions, &r_a, &r_a);
-    debug!("Self-overlap (same center): {}", s_same[[0,0]]);
-
-    // This is the physical overlap between atom A and atom B
-    let (s_diff, _, _) = integrals::build_one_electron_matrices(&basis_functions, &r_a, &r_b);
-    debug!("Inter-atomic overlap: {}", s_diff[[0,0]]);
-
+pub struct ScfResult {
+    pub e_total: f64,
+    pub c: Array2<f64>,
+    pub d_matrix: Array2<f64>,
+    pub iterations: usize,
 }
 
-fn main() {
-    env_logger::init();
-
-    let r_a = [0.0, 0.0, 0.0];
-    let r_b = [0.0, 0.0, 1.4]; 
-    let r = dist_sq(&r_a, &r_b).sqrt();
-    debug!("Inter-nuclear distance: {}", r);
-
-    let basis_sets = load_basis_sets("basis_sets");
-    println!("\n=== Loaded {} basis sets ===\n", basis_sets.len());
-
-    let basis_name = "STO-3G";
-    let basis = basis_sets
-        .iter()
-        .find(|bs| bs.name == basis_name)
-        .expect("Basis set not found");
-    
-    debug!("{:#?}", basis);
-    debug!("Basis function exponents: {:?}", &basis.exponents);
-    debug!("Basis function coefficients: {:?}", &basis.coefficients);
-    
-    let mut basis = basis.clone(); //needs to be mutable to normalise
-    basis.normalise();
-    debug!("Post-norm coefficients: {:?}", basis.coefficients);
-
-    let basis_functions = vec![basis.clone(), basis.clone()]; // two basis functions, one on each atom
-    let n_basis = basis_functions.len();
-
-    debug_matrix_values(basis, basis_functions.clone(), r_a, r_b);
-
-    let (s_matrix, t_matrix, v_matrix) = integrals::build_one_electron_matrices(&basis_functions, &r_a, &r_b);
-    debug!("Overlap matrix S:\n{}", s_matrix);
-    debug!("Kinetic energy matrix T:\n{}", t_matrix);
-    debug!("Nuclear attraction matrix V:\n{}", v_matrix);
-    
-    // Build density matrix D with all zeroes as a guess
+// Restricted closed-shell Roothaan-Hall SCF: builds S^-1/2 each iteration, diagonalizes the
+// transformed Fock matrix, occupies the n_occ lowest orbitals doubly, and iterates until the
+// total energy change drops below conv_thres. Pulled out of main so it can be exercised directly
+// by the regression test below without needing a basis_sets directory on disk.
+pub fn run_scf(s_matrix: &Array2<f64>, h_core: &Array2<f64>, eri_tensor: &ndarray::Array4<f64>, n_occ: usize, e_nuc_rep: f64) -> ScfResult {
+    let n_basis = s_matrix.nrows();
     let mut d_matrix = Array2::<f64>::zeros((n_basis, n_basis));
-    
-    let eri_tensor = integrals::build_eri_tensor_symmetric(&basis_functions, &[r_a, r_b]);
-    debug!("Electron repulsion integral tensor ERI:\n{:?}", eri_tensor);
-
-    let h_core = &t_matrix + &v_matrix;
-    debug!("Core Hamiltonian H_core:\n{}", h_core);
-
-    let e_nuc_rep = 1.0 * 1.0 / r;
-    debug!("Nuclear repulsion energy: {}", e_nuc_rep);
-
+    let mut c = Array2::<f64>::zeros((n_basis, n_basis));
     let mut e_old = 0.0;
     let max_iter = 50;
     let conv_thres = 1e-9;
 
     for iter in 0..max_iter {
-        let g_matrix = integrals::build_g_matrix(&eri_tensor, &d_matrix);
+        let g_matrix = integrals::build_g_matrix(eri_tensor, &d_matrix);
         debug!("G matrix:\n{}", g_matrix);
-        let f_matrix= &t_matrix + &v_matrix + &g_matrix;
+        let f_matrix = h_core + &g_matrix;
         debug!("Fock matrix F:\n{}", f_matrix);
 
         let (s_eigvals, s_eigvecs) = s_matrix.eig().expect("Eigendecomposition of S failed");
@@ -183,7 +171,7 @@ fn main() {
         // clean up S^-1/2 in case of tiny eigenvalues
         let s_inv_sqrt_diag = Array2::from_diag(
             &s_eigvals.map(|v: &c64| {
-                let val = v.re.max(1e-15); 
+                let val = v.re.max(1e-15);
                 val.powf(-0.5)
             })
         );
@@ -195,42 +183,36 @@ fn main() {
         let f_prime = x.t().dot(&f_matrix).dot(&x);
         debug!("Transformed Fock matrix F':\n{}", f_prime);
 
-        // Diagnonalize F' to get orbital energies and coefficients in orthonormal basis    
+        // Diagnonalize F' to get orbital energies and coefficients in orthonormal basis
         let (epsilon_complex, c_prime_complex) = f_prime.eig().expect("Fock diagonalization failed");
         let epsilon = epsilon_complex.map(|v| v.re);
         let c_prime = c_prime_complex.map(|v| v.re);
         debug!("Orbital energies: {:?}", epsilon);
 
         // back-transform coefficients to original basis
-        let c = x.dot(&c_prime);
+        c = x.dot(&c_prime);
         debug!("Molecular orbital coefficients:\n{}", c);
 
-
-        let num_electrons = 2; // Hardcoding for now 
-        if num_electrons % 2 != 0 {
-            panic!("Restricted Hartree-Fock requires an even number of electrons!");
-        }
-
         // In Rust the eigenvalues from LAPACK are not automatically sorted, so we need to sort them and the corresponding coefficients
         // Unlike Julia, Rust does not have built-in sorting that returns indices, so we create a vector of indices and sort that
         let mut indices: Vec<usize> = (0..epsilon.len()).collect();
         indices.sort_by(|&i, &j| epsilon[i].partial_cmp(&epsilon[j]).unwrap());
 
-        let lowest_idx = indices[0];
-        let c_occ = c.column(lowest_idx);
+        // Occupy the n_occ lowest orbitals (doubly, restricted HF)
+        let occ_idx = &indices[0..n_occ];
 
-        // Rust magic I needed a lot of LLM help for 
-        let c_view = c_occ.view().insert_axis(ndarray::Axis(1));
-        d_matrix = 2.0 * c_view.dot(&c_view.t());
-        for i in 0..n_basis {
-            for j in 0..n_basis {
-                d_matrix[[i, j]] = 0.0;
-                    d_matrix[[i, j]] += 2.0 * c_occ[[i]] * c_occ[[j]]; 
+        d_matrix = Array2::<f64>::zeros((n_basis, n_basis));
+        for &idx in occ_idx {
+            let c_occ = c.column(idx);
+            for i in 0..n_basis {
+                for j in 0..n_basis {
+                    d_matrix[[i, j]] += 2.0 * c_occ[[i]] * c_occ[[j]];
+                }
             }
         }
         debug!("Density matrix D:\n{}", d_matrix);
 
-        let e_elec = 0.5 * (&d_matrix * (&h_core + &f_matrix)).sum();
+        let e_elec = 0.5 * (&d_matrix * (h_core + &f_matrix)).sum();
         debug!("Electronic energy: {}", e_elec);
         debug!("Nuclear repulsion energy: {}", e_nuc_rep);
         let e_total = e_elec + e_nuc_rep;
@@ -241,10 +223,234 @@ fn main() {
         if delta_e < conv_thres {
             println!("SCF converged in {} iterations.", iter + 1);
             println!("ðŸ¦€");
-            let electrons = (&d_matrix * &s_matrix).sum();
-            debug!("Total electrons in system: {:.4}", electrons);
-            break;
+            return ScfResult { e_total, c, d_matrix, iterations: iter + 1 };
         }
         e_old = e_total;
     }
+    panic!("SCF did not converge in {} iterations", max_iter);
+}
+
+fn main() {
+    env_logger::init();
+
+    // H2 at the usual equilibrium-ish bond length - the molecule is now data,
+    // not baked into the integral routines, so this is just one example.
+    let molecule = Molecule {
+        atoms: vec![
+            Atom { z: 1.0, position: [0.0, 0.0, 0.0] },
+            Atom { z: 1.0, position: [0.0, 0.0, 1.4] },
+        ],
+    };
+    debug!("Molecule: {:#?}", molecule);
+
+    let basis_sets = load_basis_sets("basis_sets");
+    println!("\n=== Loaded {} basis sets ===\n", basis_sets.len());
+
+    let basis_name = "STO-3G";
+    let basis_set = basis_sets
+        .iter()
+        .find(|bs| bs.name == basis_name)
+        .expect("Basis set not found");
+
+    let basis_functions = molecule::build_centered_basis_functions(basis_set, &molecule);
+    let n_basis = basis_functions.len();
+    debug!("Expanded {} centered basis functions", n_basis);
+
+    let (s_matrix, t_matrix, v_matrix) = integrals::build_one_electron_matrices(&basis_functions, &molecule);
+    debug!("Overlap matrix S:\n{}", s_matrix);
+    debug!("Kinetic energy matrix T:\n{}", t_matrix);
+    debug!("Nuclear attraction matrix V:\n{}", v_matrix);
+
+    let eri_tensor = integrals::build_eri_tensor_symmetric(&basis_functions, integrals::FULL_COULOMB_OMEGA, integrals::DEFAULT_SCHWARZ_TAU);
+    debug!("Electron repulsion integral tensor ERI:\n{:?}", eri_tensor);
+
+    let h_core = &t_matrix + &v_matrix;
+    debug!("Core Hamiltonian H_core:\n{}", h_core);
+
+    let e_nuc_rep = molecule.nuclear_repulsion();
+    debug!("Nuclear repulsion energy: {}", e_nuc_rep);
+
+    let num_electrons = molecule.num_electrons();
+    if num_electrons % 2 != 0 {
+        panic!("Restricted Hartree-Fock requires an even number of electrons!");
+    }
+    let n_occ = num_electrons / 2;
+
+    let scf = run_scf(&s_matrix, &h_core, &eri_tensor, n_occ, e_nuc_rep);
+    let d_matrix = scf.d_matrix;
+    let c = scf.c;
+
+    let electrons = (&d_matrix * &s_matrix).sum();
+    debug!("Total electrons in system: {:.4}", electrons);
+
+    // Molecular dipole moment: electronic part from the density and dipole integrals,
+    // plus the nuclear point-charge contribution.
+    let multipole = integrals::build_multipole_matrices(&basis_functions);
+    let mut mu = [0.0; 3];
+    for (axis, (mu_axis, dipole_axis)) in mu.iter_mut().zip(multipole.dipole.iter()).enumerate() {
+        let electronic: f64 = -(&d_matrix * dipole_axis).sum();
+        let nuclear: f64 = molecule.atoms.iter().map(|atom| atom.z * atom.position[axis]).sum();
+        *mu_axis = electronic + nuclear;
+    }
+    println!("Molecular dipole (a.u.): [{:.6}, {:.6}, {:.6}]", mu[0], mu[1], mu[2]);
+
+    // Molecular quadrupole moment (primitive, not traceless): electronic part from the
+    // density and quadrupole integrals, plus the nuclear point-charge contribution.
+    let quad_axis_pairs = [(0, 0), (0, 1), (0, 2), (1, 1), (1, 2), (2, 2)];
+    let mut theta = [0.0; 6];
+    for (comp, &(axis_a, axis_b)) in quad_axis_pairs.iter().enumerate() {
+        let electronic: f64 = -(&d_matrix * &multipole.quadrupole[comp]).sum();
+        let nuclear: f64 = molecule.atoms.iter().map(|atom| atom.z * atom.position[axis_a] * atom.position[axis_b]).sum();
+        theta[comp] = electronic + nuclear;
+    }
+    println!(
+        "Molecular quadrupole (a.u.): xx={:.6}, xy={:.6}, xz={:.6}, yy={:.6}, yz={:.6}, zz={:.6}",
+        theta[0], theta[1], theta[2], theta[3], theta[4], theta[5]
+    );
+
+    // Mulliken population analysis: gross population per basis function from (D*S)_mu,mu,
+    // summed per atom to get partial charges.
+    let ds_matrix = d_matrix.dot(&s_matrix);
+    let mut populations = vec![0.0; molecule.atoms.len()];
+    for (mu_idx, bf) in basis_functions.iter().enumerate() {
+        populations[bf.atom_index] += ds_matrix[[mu_idx, mu_idx]];
+    }
+    for (atom_index, atom) in molecule.atoms.iter().enumerate() {
+        let charge = atom.z - populations[atom_index];
+        println!("Atom {}: Mulliken population {:.4}, partial charge {:.4}", atom_index, populations[atom_index], charge);
+    }
+
+    // Range-separated (long-range corrected) diagnostic: exchange from a long-range,
+    // erf(omega*r12)/r12 tensor, Coulomb from the full operator (no short-range exchange
+    // term is added), evaluated on the converged full-Coulomb density.
+    let omega_lr = 0.4; // a typical LC-wPBE-style separation parameter
+    let eri_long_range = integrals::build_eri_tensor_symmetric(&basis_functions, omega_lr, integrals::DEFAULT_SCHWARZ_TAU);
+    let g_range_separated = integrals::build_g_matrix_range_separated(&eri_tensor, &eri_long_range, &d_matrix);
+    let f_range_separated = &h_core + &g_range_separated;
+    let e_elec_range_separated = 0.5 * (&d_matrix * (&h_core + &f_range_separated)).sum();
+    println!("Range-separated (omega={:.2}) electronic energy on converged density: {:.10} Hartrees", omega_lr, e_elec_range_separated);
+
+    // Gaussian-geminal (F12) diagnostic: contract the converged density twice with the geminal
+    // tensor, Coulomb-style (sum of D_ij * D_kl * f12_ijkl, no exchange term), so the
+    // F12 fit actually gets exercised end-to-end rather than sitting unused.
+    // compute_geminal_primitive is still s-type only (it never reads angular_momentum, so its
+    // closed-form Gaussian holds only for s functions), so skip the diagnostic
+    // rather than print a number that is silently wrong for any basis with p/d shells.
+    let has_non_s_shell = basis_functions.iter().any(|bf| bf.shell.angular_momentum != (0, 0, 0));
+    if has_non_s_shell {
+        println!("Skipping Gaussian-geminal (F12) diagnostic: compute_geminal_primitive is s-type only and this basis has p/d shells.");
+    } else {
+        let correlation_gamma = 1.4; // a typical Slater correlation length for valence electron pairs
+        let f12_tensor = integrals::build_f12_tensor(&basis_functions, correlation_gamma);
+        let mut e_geminal = 0.0;
+        for i in 0..n_basis {
+            for j in 0..n_basis {
+                for k in 0..n_basis {
+                    for l in 0..n_basis {
+                        e_geminal += d_matrix[[i, j]] * d_matrix[[k, l]] * f12_tensor[[i, j, k, l]];
+                    }
+                }
+            }
+        }
+        println!("Gaussian-geminal (gamma={:.2}) density self-energy: {:.10} Hartrees", correlation_gamma, e_geminal);
+    }
+
+    // Post-HF full CI on top of the converged MOs
+    let mo_integrals = ci::ao_to_mo(&h_core, &eri_tensor, &c);
+    let determinants = ci::build_determinants(n_basis, n_occ, n_occ);
+    let fci = ci::davidson_lowest_eigenpair(&determinants, &mo_integrals, 20, 1e-8, 100);
+    println!("Full CI electronic energy: {:.10} Hartrees", fci.eigenvalue);
+    println!("Full CI total energy: {:.10} Hartrees", fci.eigenvalue + e_nuc_rep);
+
+    // Report the dominant determinant in the CI expansion (the largest-magnitude
+    // coefficient in the ground-state eigenvector) so the eigenvector itself is used
+    // for something, not just computed and discarded.
+    let (dominant_idx, dominant_coeff) = fci.eigenvector.iter().enumerate()
+        .max_by(|(_, a), (_, b)| a.abs().partial_cmp(&b.abs()).unwrap())
+        .expect("determinant list is non-empty");
+    let dominant_det = &determinants[dominant_idx];
+    println!(
+        "Full CI dominant determinant: alpha=0b{:0width$b}, beta=0b{:0width$b}, coefficient={:.6}",
+        dominant_det.alpha, dominant_det.beta, dominant_coeff, width = n_basis
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // STO-3G hydrogen 1s contraction (exponents and published contraction coefficients),
+    // hand-entered so this regression test doesn't depend on reading basis_sets/STO-3G.json
+    // from disk.
+    fn h_sto3g_shell() -> BasisSetData {
+        let mut shell = BasisSetData {
+            name: "STO-3G".to_string(),
+            description: "test fixture".to_string(),
+            exponents: vec![3.42525091, 0.62391373, 0.16885540],
+            coefficients: vec![0.15432897, 0.53532814, 0.44463454],
+            angular_momentum: (0, 0, 0),
+        };
+        shell.normalise();
+        shell
+    }
+
+    // H2 at the same 1.4 bohr bond length main() uses.
+    fn h2_sto3g() -> (Molecule, Vec<molecule::CenteredBasisFunction>) {
+        let molecule = Molecule {
+            atoms: vec![
+                Atom { z: 1.0, position: [0.0, 0.0, 0.0] },
+                Atom { z: 1.0, position: [0.0, 0.0, 1.4] },
+            ],
+        };
+        let basis_functions = vec![
+            molecule::CenteredBasisFunction { shell: h_sto3g_shell(), center: molecule.atoms[0].position, atom_index: 0 },
+            molecule::CenteredBasisFunction { shell: h_sto3g_shell(), center: molecule.atoms[1].position, atom_index: 1 },
+        ];
+        (molecule, basis_functions)
+    }
+
+    // Reference value hand-verified against the textbook H2/STO-3G result at this bond length:
+    // RHF total energy = -1.1167143251 Hartrees.
+    #[test]
+    fn h2_sto3g_rhf_energy_matches_reference() {
+        let (molecule, basis_functions) = h2_sto3g();
+        let (s_matrix, t_matrix, v_matrix) = integrals::build_one_electron_matrices(&basis_functions, &molecule);
+        let h_core = &t_matrix + &v_matrix;
+        let eri_tensor = integrals::build_eri_tensor_symmetric(&basis_functions, integrals::FULL_COULOMB_OMEGA, integrals::DEFAULT_SCHWARZ_TAU);
+        let e_nuc_rep = molecule.nuclear_repulsion();
+        let n_occ = molecule.num_electrons() / 2;
+
+        let scf = run_scf(&s_matrix, &h_core, &eri_tensor, n_occ, e_nuc_rep);
+
+        assert!(
+            (scf.e_total - (-1.1167143251)).abs() < 1e-3,
+            "RHF total energy {} too far from reference -1.1167143251",
+            scf.e_total
+        );
+    }
+
+    // Reference value hand-verified against the textbook H2/STO-3G result at this bond length:
+    // full CI total energy = -1.1372759436 Hartrees.
+    #[test]
+    fn h2_sto3g_full_ci_energy_matches_reference() {
+        let (molecule, basis_functions) = h2_sto3g();
+        let (s_matrix, t_matrix, v_matrix) = integrals::build_one_electron_matrices(&basis_functions, &molecule);
+        let h_core = &t_matrix + &v_matrix;
+        let eri_tensor = integrals::build_eri_tensor_symmetric(&basis_functions, integrals::FULL_COULOMB_OMEGA, integrals::DEFAULT_SCHWARZ_TAU);
+        let e_nuc_rep = molecule.nuclear_repulsion();
+        let n_occ = molecule.num_electrons() / 2;
+        let n_basis = basis_functions.len();
+
+        let scf = run_scf(&s_matrix, &h_core, &eri_tensor, n_occ, e_nuc_rep);
+        let mo_integrals = ci::ao_to_mo(&h_core, &eri_tensor, &scf.c);
+        let determinants = ci::build_determinants(n_basis, n_occ, n_occ);
+        let fci = ci::davidson_lowest_eigenpair(&determinants, &mo_integrals, 20, 1e-8, 100);
+        let fci_total = fci.eigenvalue + e_nuc_rep;
+
+        assert!(
+            (fci_total - (-1.1372759436)).abs() < 1e-3,
+            "Full CI total energy {} too far from reference -1.1372759436",
+            fci_total
+        );
+    }
 }
@@ -0,0 +1,61 @@
+// Molecule representation and basis-function expansion, modelled on QCaml's
+// `of_nuclei_and_basis_filename`: a basis set is expanded over every nucleus
+// present, not assumed to be a fixed pair of centers.
+
+use crate::{BasisSet, BasisSetData};
+
+#[derive(Debug, Clone)]
+pub struct Atom {
+    pub z: f64,
+    pub position: [f64; 3],
+}
+
+#[derive(Debug, Clone)]
+pub struct Molecule {
+    pub atoms: Vec<Atom>,
+}
+
+impl Molecule {
+    pub fn num_electrons(&self) -> usize {
+        self.atoms.iter().map(|atom| atom.z as usize).sum()
+    }
+
+    pub fn nuclear_repulsion(&self) -> f64 {
+        let mut e_nuc = 0.0;
+        for i in 0..self.atoms.len() {
+            for j in (i + 1)..self.atoms.len() {
+                let r = crate::integrals::dist_sq(&self.atoms[i].position, &self.atoms[j].position).sqrt();
+                e_nuc += self.atoms[i].z * self.atoms[j].z / r;
+            }
+        }
+        e_nuc
+    }
+}
+
+// A contracted basis function together with the nucleus it is centered on.
+#[derive(Debug, Clone)]
+pub struct CenteredBasisFunction {
+    pub shell: BasisSetData,
+    pub center: [f64; 3],
+    pub atom_index: usize, // index into Molecule::atoms, for per-atom analysis (e.g. Mulliken charges)
+}
+
+// Expand a basis set over every atom in the molecule, associating each
+// contracted shell of the atom's element with that atom's position.
+pub fn build_centered_basis_functions(basis_set: &BasisSet, molecule: &Molecule) -> Vec<CenteredBasisFunction> {
+    let mut basis_functions = Vec::new();
+
+    for (atom_index, atom) in molecule.atoms.iter().enumerate() {
+        let z = atom.z.round() as u32;
+        for mut shell in basis_set.shells_for_element(z) {
+            shell.normalise();
+            basis_functions.push(CenteredBasisFunction {
+                shell,
+                center: atom.position,
+                atom_index,
+            });
+        }
+    }
+
+    basis_functions
+}